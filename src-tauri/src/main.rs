@@ -1,8 +1,27 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::{collections::HashMap, io::Read, sync::{Arc, Mutex}};
+use std::{collections::HashMap, fs::File, io::{BufWriter, Read, Write}, net::{TcpStream, ToSocketAddrs}, path::PathBuf, sync::{Arc, Mutex}, time::{Duration, Instant, SystemTime, UNIX_EPOCH}};
 use tauri::{Emitter, Manager, State, Url};
 use serde::{Deserialize, Serialize};
+use polling::{Event, Poller};
+use slab::Slab;
+
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, RawFd};
+#[cfg(windows)]
+use std::os::windows::io::{AsRawSocket, RawSocket};
+
+/// Platform raw readable descriptor registered with the poller. Unix PTY masters
+/// and sockets are plain fds; on Windows the transports we poll are sockets.
+#[cfg(unix)]
+type RawDesc = RawFd;
+#[cfg(windows)]
+type RawDesc = RawSocket;
+
+#[cfg(unix)]
+fn socket_desc(s: &TcpStream) -> RawDesc { s.as_raw_fd() }
+#[cfg(windows)]
+fn socket_desc(s: &TcpStream) -> RawDesc { s.as_raw_socket() }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "lowercase")]
@@ -25,13 +44,491 @@ struct PendingActions(Mutex<Vec<TelnetAction>>);
 // ===== PTY support =====
 use portable_pty::{native_pty_system, CommandBuilder, PtyPair, PtySize};
 #[derive(Default)]
-struct PtyRegistry(Mutex<HashMap<String, PtyEntry>>);
+struct PtyRegistry(Mutex<HashMap<String, SessionEntry>>);
+
+/// A registry entry: the transport plus the metadata shared across every
+/// session type — a spawn-time baseline for timing, the last known window size,
+/// and an optional recorder that can be toggled on and off mid-session.
+struct SessionEntry {
+  kind: Session,
+  start: Instant,
+  cols: u16,
+  rows: u16,
+  recorder: Arc<Mutex<Option<Recorder>>>,
+}
+
+/// A live session tracked by [`PtyRegistry`]. Every variant shares the same id
+/// scheme and the `write_pty`/`resize_pty`/`kill_pty` surface; they only differ
+/// in how bytes are delivered to and from the remote end.
+enum Session {
+  /// A child process driven over a real PTY (e.g. the external `telnet` binary).
+  Pty(PtyEntry),
+  /// A telnet client spoken directly over a `TcpStream`, with no child process.
+  Native(NativeSession),
+  /// A plain TCP endpoint whose bytes are streamed through untouched.
+  Raw(RawSession),
+}
+
 struct PtyEntry {
   child: Box<dyn portable_pty::Child + Send>,
   pair: PtyPair,
   writer: Box<dyn std::io::Write + Send>,
 }
 
+/// Write half of a native telnet session. The socket is shared with the
+/// reactor's negotiation-reply path through this `Arc<Mutex<_>>`, so keystrokes
+/// from `write_pty`, window sizes from `resize_pty`, and option replies can't
+/// interleave mid-sequence.
+struct NativeSession {
+  writer: Arc<Mutex<TcpStream>>,
+}
+
+/// Write half of a raw TCP passthrough session. No telnet or PTY framing is
+/// applied; bytes go out exactly as `write_pty` receives them.
+struct RawSession {
+  writer: TcpStream,
+}
+
+// ===== Native telnet protocol =====
+// Telnet command bytes (RFC 854) and the handful of options we negotiate.
+const IAC: u8 = 255;
+const DONT: u8 = 254;
+const DO: u8 = 253;
+const WONT: u8 = 252;
+const WILL: u8 = 251;
+const SB: u8 = 250;
+const SE: u8 = 240;
+const OPT_SGA: u8 = 3;
+const OPT_NAWS: u8 = 31;
+
+/// Options we are willing to turn on when the peer asks (`DO`) or announces
+/// (`WILL`). Everything else is refused so the stream settles quickly.
+fn we_support(opt: u8) -> bool {
+  matches!(opt, OPT_SGA | OPT_NAWS)
+}
+
+/// Double every `0xFF` byte so literal data can't be mistaken for an `IAC`.
+fn escape_iac(data: &[u8]) -> Vec<u8> {
+  let mut out = Vec::with_capacity(data.len());
+  for &b in data {
+    out.push(b);
+    if b == IAC {
+      out.push(IAC);
+    }
+  }
+  out
+}
+
+/// Build an `IAC SB NAWS <width> <height> IAC SE` subnegotiation. Dimensions are
+/// 16-bit big-endian and any `0xFF` byte among them is doubled.
+fn naws_subnegotiation(cols: u16, rows: u16) -> Vec<u8> {
+  let mut out = vec![IAC, SB, OPT_NAWS];
+  for b in [(cols >> 8) as u8, (cols & 0xff) as u8, (rows >> 8) as u8, (rows & 0xff) as u8] {
+    out.push(b);
+    if b == IAC {
+      out.push(IAC);
+    }
+  }
+  out.push(IAC);
+  out.push(SE);
+  out
+}
+
+enum TelnetState {
+  Data,
+  Iac,
+  Command(u8),
+  Subneg,
+  SubnegIac,
+}
+
+/// Incremental telnet stream decoder. `feed` splits incoming bytes into the
+/// application data that should reach the frontend and the negotiation replies
+/// that must be written back to the peer.
+struct TelnetParser {
+  state: TelnetState,
+  /// Window size given at spawn, replayed as a NAWS subnegotiation once the peer
+  /// agrees to our `WILL NAWS` offer with `DO NAWS`. Later `resize_pty` calls do
+  /// not update these fields.
+  cols: u16,
+  rows: u16,
+}
+
+impl TelnetParser {
+  fn new(cols: u16, rows: u16) -> Self {
+    TelnetParser { state: TelnetState::Data, cols, rows }
+  }
+
+  fn feed(&mut self, input: &[u8], out: &mut Vec<u8>, replies: &mut Vec<u8>) {
+    for &b in input {
+      match self.state {
+        TelnetState::Data => {
+          if b == IAC {
+            self.state = TelnetState::Iac;
+          } else {
+            out.push(b);
+          }
+        }
+        TelnetState::Iac => match b {
+          IAC => {
+            // Escaped 0xFF in the data stream collapses to a single byte.
+            out.push(IAC);
+            self.state = TelnetState::Data;
+          }
+          WILL | WONT | DO | DONT => self.state = TelnetState::Command(b),
+          SB => self.state = TelnetState::Subneg,
+          _ => self.state = TelnetState::Data,
+        },
+        TelnetState::Command(cmd) => {
+          self.respond(cmd, b, replies);
+          self.state = TelnetState::Data;
+        }
+        TelnetState::Subneg => {
+          if b == IAC {
+            self.state = TelnetState::SubnegIac;
+          }
+        }
+        TelnetState::SubnegIac => {
+          // `IAC SE` closes the subnegotiation; `IAC IAC` is escaped data we drop.
+          self.state = if b == SE { TelnetState::Data } else { TelnetState::Subneg };
+        }
+      }
+    }
+  }
+
+  fn respond(&self, cmd: u8, opt: u8, replies: &mut Vec<u8>) {
+    // We offer `WILL NAWS` at connect; the peer's `DO NAWS` is the acknowledgement
+    // that unlocks the window-size subnegotiation (RFC 1073). Reply with the size
+    // rather than re-offering `WILL`, which would loop the negotiation.
+    if cmd == DO && opt == OPT_NAWS {
+      replies.extend_from_slice(&naws_subnegotiation(self.cols, self.rows));
+      return;
+    }
+    let reply = match cmd {
+      DO => if we_support(opt) { WILL } else { WONT },
+      WILL => if we_support(opt) { DO } else { DONT },
+      DONT => WONT,
+      WONT => DONT,
+      _ => return,
+    };
+    replies.extend_from_slice(&[IAC, reply, opt]);
+  }
+}
+
+// ===== Session recording (asciinema v2) =====
+/// Writes an asciinema v2 `.cast` file. The header is emitted on creation and
+/// every event is flushed immediately so a crash still leaves a valid partial
+/// recording. Event timestamps are relative to the session's spawn baseline.
+struct Recorder {
+  file: BufWriter<File>,
+  start: Instant,
+}
+
+impl Recorder {
+  fn create(path: &str, cols: u16, rows: u16, start: Instant) -> std::io::Result<Self> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let mut file = BufWriter::new(File::create(path)?);
+    let header = serde_json::json!({ "version": 2, "width": cols, "height": rows, "timestamp": timestamp });
+    writeln!(file, "{header}")?;
+    file.flush()?;
+    Ok(Recorder { file, start })
+  }
+
+  /// Append one `[time, code, data]` event and flush. `code` is `"o"` for output
+  /// and `"r"` for a resize.
+  fn event(&mut self, code: &str, data: &str) {
+    let elapsed = self.start.elapsed().as_secs_f64();
+    let line = serde_json::json!([elapsed, code, data]);
+    let _ = writeln!(self.file, "{line}");
+    let _ = self.file.flush();
+  }
+}
+
+/// Build the shared recorder handle for a new session, opening the `.cast` file
+/// up front when `record_path` is set so a spawn-time failure is reported to the
+/// caller rather than swallowed in the reader thread.
+fn new_recorder(record_path: Option<&str>, cols: u16, rows: u16, start: Instant) -> Result<Arc<Mutex<Option<Recorder>>>, String> {
+  let recorder = match record_path {
+    Some(path) => Some(Recorder::create(path, cols, rows, start).map_err(|e| format!("record {path}: {e}"))?),
+    None => None,
+  };
+  Ok(Arc::new(Mutex::new(recorder)))
+}
+
+/// Record an output chunk if a recorder is attached. Shared by every reader path.
+fn record_output(recorder: &Arc<Mutex<Option<Recorder>>>, data: &str) {
+  if let Ok(mut guard) = recorder.lock() {
+    if let Some(rec) = guard.as_mut() {
+      rec.event("o", data);
+    }
+  }
+}
+
+/// Upper bound on how long a control-path write waits on a back-pressuring peer
+/// before giving up, so a wedged socket can't tie up a command thread forever.
+const WRITE_DEADLINE: Duration = Duration::from_secs(10);
+
+/// Write every byte to a socket that may be in non-blocking mode. The reactor
+/// sockets are non-blocking and a native session's write half shares that same
+/// file description, so the control-path writes (`write_pty`/`resize_pty`,
+/// negotiation replies) must tolerate short writes. A `WouldBlock` yields the
+/// thread with a short sleep rather than a busy spin, and a peer that never
+/// drains is bounded by [`WRITE_DEADLINE`]. Callers must not hold the session
+/// registry lock across this call.
+fn write_all_nonblocking(sock: &mut TcpStream, mut data: &[u8]) -> std::io::Result<()> {
+  let deadline = Instant::now() + WRITE_DEADLINE;
+  while !data.is_empty() {
+    match sock.write(data) {
+      Ok(0) => return Err(std::io::ErrorKind::WriteZero.into()),
+      Ok(n) => data = &data[n..],
+      Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+        if Instant::now() >= deadline {
+          return Err(std::io::ErrorKind::TimedOut.into());
+        }
+        std::thread::sleep(Duration::from_millis(1));
+      }
+      Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+      Err(e) => return Err(e),
+    }
+  }
+  Ok(())
+}
+
+// ===== Single-reactor read loop =====
+/// Per-source state held in the reactor's slab, keyed by the poll token. Only
+/// socket-backed sessions (native telnet and raw TCP) are driven by the reactor.
+/// PTY children keep a dedicated reader thread — see the scope note in
+/// [`start_pty`] for why their master isn't registered here.
+struct Source {
+  id: String,
+  desc: RawDesc,
+  /// Read half of the session socket, set non-blocking in [`Reactor::register`]
+  /// so the reactor never blocks on a spurious readiness hint.
+  socket: TcpStream,
+  recorder: Arc<Mutex<Option<Recorder>>>,
+  /// Present only for native telnet sessions, which decode negotiation inline.
+  parser: Option<TelnetParser>,
+  /// Shared write half for telnet negotiation replies (native sessions only).
+  /// The same `Arc` backs `NativeSession::writer`, so every writer serialises
+  /// through one mutex and sequences can't interleave mid-stream.
+  replies: Option<Arc<Mutex<TcpStream>>>,
+  app: tauri::AppHandle,
+}
+
+/// A single background thread drives every socket session's reads through one
+/// `polling::Poller`. Sources register their socket on spawn and are drained
+/// only when the poller reports them readable, so idle sessions cost nothing
+/// beyond their slab entry — no per-session thread or stack.
+struct Reactor {
+  poller: Arc<Poller>,
+  sources: Arc<Mutex<Slab<Source>>>,
+}
+
+impl Reactor {
+  fn start() -> std::io::Result<Arc<Self>> {
+    let poller = Arc::new(Poller::new()?);
+    let sources: Arc<Mutex<Slab<Source>>> = Arc::new(Mutex::new(Slab::new()));
+    let reactor = Arc::new(Reactor { poller: poller.clone(), sources: sources.clone() });
+    std::thread::spawn(move || reactor_loop(poller, sources));
+    Ok(reactor)
+  }
+
+  /// Register a readable socket and wake the poller so it is picked up without
+  /// waiting for the in-flight `wait` to return on its own.
+  fn register(&self, source: Source) -> Result<(), String> {
+    let desc = source.desc;
+    // Non-blocking reads keep a spurious wakeup from ever stalling the reactor.
+    source.socket.set_nonblocking(true).map_err(|e| format!("set nonblocking: {e}"))?;
+    let mut sources = self.sources.lock().map_err(|_| "lock sources".to_string())?;
+    let key = sources.insert(source);
+    if let Err(e) = self.poller.add(desc, Event::readable(key)) {
+      sources.remove(key);
+      return Err(format!("poll add: {e}"));
+    }
+    let _ = self.poller.notify();
+    Ok(())
+  }
+
+  /// Deregister the source for `id`, deleting it from the poller before the
+  /// caller closes the underlying transport so no read lands on a reused fd.
+  fn deregister(&self, id: &str) {
+    let Ok(mut sources) = self.sources.lock() else { return };
+    let key = sources.iter().find(|(_, s)| s.id == id).map(|(k, _)| k);
+    if let Some(key) = key {
+      let source = sources.remove(key);
+      let _ = self.poller.delete(source.desc);
+    }
+  }
+}
+
+fn reactor_loop(poller: Arc<Poller>, sources: Arc<Mutex<Slab<Source>>>) {
+  let mut events = Vec::new();
+  let mut buf = [0u8; 8192];
+  loop {
+    events.clear();
+    if poller.wait(&mut events, None).is_err() {
+      break;
+    }
+    for event in &events {
+      let key = event.key;
+      let mut guard = match sources.lock() {
+        Ok(g) => g,
+        Err(_) => return,
+      };
+      let Some(source) = guard.get_mut(key) else { continue };
+      // The socket is non-blocking, so this read returns at once — either data,
+      // EOF, or `WouldBlock` on a spurious wakeup — and never stalls the lock.
+      let finished = match source.socket.read(&mut buf) {
+        Ok(0) => true,
+        Ok(n) => { drain_source(source, &buf[..n]); false }
+        Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => false,
+        Err(_) => true,
+      };
+      if finished {
+        let source = guard.remove(key);
+        let _ = poller.delete(source.desc);
+        drop(guard);
+        let _ = source.app.emit("pty://exit", &PtyExit { id: source.id });
+      } else {
+        // Interest is oneshot; re-arm for the next readiness signal.
+        let _ = poller.modify(source.desc, Event::readable(key));
+      }
+    }
+  }
+}
+
+/// Process one chunk from a readable source: decode telnet negotiation for
+/// native sessions, record the output, and emit it on `pty://data`.
+fn drain_source(source: &mut Source, input: &[u8]) {
+  let data = if let Some(parser) = source.parser.as_mut() {
+    let mut out = Vec::new();
+    let mut replies = Vec::new();
+    parser.feed(input, &mut out, &mut replies);
+    if !replies.is_empty() {
+      if let Some(writer) = source.replies.as_ref() {
+        if let Ok(mut sock) = writer.lock() {
+          let _ = write_all_nonblocking(&mut sock, &replies);
+        }
+      }
+    }
+    if out.is_empty() {
+      return;
+    }
+    String::from_utf8_lossy(&out).to_string()
+  } else {
+    String::from_utf8_lossy(input).to_string()
+  };
+  record_output(&source.recorder, &data);
+  let _ = source.app.emit("pty://data", &PtyData { id: source.id.clone(), data });
+}
+
+// ===== Connection profiles =====
+/// A reusable launch template: an executable plus an argument list whose
+/// `{host}`/`{port}`/`{label}` placeholders are filled in at spawn time, along
+/// with extra environment variables and an optional working directory. Profiles
+/// turn the tool into a general terminal multiplexer (ssh, mosh, serial, …)
+/// while the built-in `telnet` profile preserves the original default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ConnectionProfile {
+  command: String,
+  #[serde(default)]
+  args: Vec<String>,
+  #[serde(default)]
+  env: HashMap<String, String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  cwd: Option<String>,
+}
+
+impl ConnectionProfile {
+  /// The built-in default, equivalent to the original hardcoded `telnet host port`.
+  fn telnet() -> Self {
+    ConnectionProfile { command: "telnet".into(), args: vec!["{host}".into(), "{port}".into()], env: HashMap::new(), cwd: None }
+  }
+
+  /// Build a [`CommandBuilder`] with every placeholder substituted.
+  fn build(&self, host: &str, port: u16, label: &str) -> CommandBuilder {
+    let mut cmd = CommandBuilder::new(&self.command);
+    for arg in &self.args {
+      cmd.arg(fill_placeholders(arg, host, port, label));
+    }
+    for (key, value) in &self.env {
+      cmd.env(key, fill_placeholders(value, host, port, label));
+    }
+    if let Some(cwd) = &self.cwd {
+      cmd.cwd(cwd);
+    }
+    cmd
+  }
+}
+
+fn fill_placeholders(template: &str, host: &str, port: u16, label: &str) -> String {
+  template
+    .replace("{host}", host)
+    .replace("{port}", &port.to_string())
+    .replace("{label}", label)
+}
+
+/// Named profiles plus their on-disk JSON backing file in the app config dir.
+struct ProfileStore {
+  profiles: Mutex<HashMap<String, ConnectionProfile>>,
+  path: PathBuf,
+}
+
+impl ProfileStore {
+  /// Load the store from `path`, tolerating a missing or unreadable file by
+  /// starting empty so a first run (or a corrupt file) still launches.
+  fn load(path: PathBuf) -> Self {
+    let profiles = std::fs::read_to_string(&path)
+      .ok()
+      .and_then(|raw| serde_json::from_str(&raw).ok())
+      .unwrap_or_default();
+    ProfileStore { profiles: Mutex::new(profiles), path }
+  }
+
+  fn persist(&self, profiles: &HashMap<String, ConnectionProfile>) -> Result<(), String> {
+    if let Some(dir) = self.path.parent() {
+      std::fs::create_dir_all(dir).map_err(|e| format!("create config dir: {e}"))?;
+    }
+    let json = serde_json::to_string_pretty(profiles).map_err(|e| format!("serialize profiles: {e}"))?;
+    std::fs::write(&self.path, json).map_err(|e| format!("write profiles: {e}"))
+  }
+
+  /// Resolve a profile by name, falling back to the built-in `telnet` template.
+  fn get(&self, name: &str) -> Option<ConnectionProfile> {
+    if let Some(profile) = self.profiles.lock().ok()?.get(name) {
+      return Some(profile.clone());
+    }
+    (name == "telnet").then(ConnectionProfile::telnet)
+  }
+}
+
+/// List the saved profiles, including the built-in `telnet` default.
+#[tauri::command]
+fn list_profiles(store: State<'_, Arc<ProfileStore>>) -> HashMap<String, ConnectionProfile> {
+  let mut all = HashMap::new();
+  all.insert("telnet".to_string(), ConnectionProfile::telnet());
+  if let Ok(guard) = store.profiles.lock() {
+    all.extend(guard.clone());
+  }
+  all
+}
+
+/// Create or overwrite a named profile and persist the store.
+#[tauri::command]
+fn save_profile(store: State<'_, Arc<ProfileStore>>, name: String, profile: ConnectionProfile) -> Result<(), String> {
+  let mut guard = store.profiles.lock().map_err(|_| "lock profiles".to_string())?;
+  guard.insert(name, profile);
+  store.persist(&guard)
+}
+
+/// Remove a named profile and persist the store. A no-op if it does not exist.
+#[tauri::command]
+fn delete_profile(store: State<'_, Arc<ProfileStore>>, name: String) -> Result<(), String> {
+  let mut guard = store.profiles.lock().map_err(|_| "lock profiles".to_string())?;
+  guard.remove(&name);
+  store.persist(&guard)
+}
+
 fn parse_telnet_url(url: &str) -> Option<TelnetLaunchRequest> {
   // Accept formats like telnet://host or telnet://host:port
   // Be tolerant to cases where only host:port is passed (without scheme)
@@ -60,10 +557,12 @@ fn consume_pending_telnet_actions(state: State<Arc<PendingActions>>) -> Vec<Teln
 fn main() {
   let pending = Arc::new(PendingActions::default());
   let ptys = Arc::new(PtyRegistry::default());
+  let reactor = Reactor::start().expect("start I/O reactor");
 
   tauri::Builder::default()
     .manage(pending.clone())
     .manage(ptys.clone())
+    .manage(reactor.clone())
     .plugin(tauri_plugin_process::init())
     .plugin(tauri_plugin_shell::init())
     .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
@@ -84,6 +583,10 @@ fn main() {
       }
     }))
     .setup(move |app| {
+      // Load persisted connection profiles from the app config dir.
+      let config_dir = app.path().app_config_dir().unwrap_or_else(|_| PathBuf::from("."));
+      app.manage(Arc::new(ProfileStore::load(config_dir.join("profiles.json"))));
+
       // Capture initial argv deep links
       let mut initial_actions: Vec<TelnetAction> = Vec::new();
       for arg in std::env::args().skip(1) { // skip binary path
@@ -103,35 +606,61 @@ fn main() {
     .invoke_handler(tauri::generate_handler![
       consume_pending_telnet_actions,
       start_pty,
+      start_tcp,
       write_pty,
       resize_pty,
-      kill_pty
+      kill_pty,
+      start_recording,
+      stop_recording,
+      list_profiles,
+      save_profile,
+      delete_profile
     ])
     .run(tauri::generate_context!())
     .expect("error while running tauri application");
 }
 
 #[tauri::command]
-async fn start_pty(app: tauri::AppHandle, state: State<'_, Arc<PtyRegistry>>, host: String, port: Option<u16>, cols: Option<u16>, rows: Option<u16>) -> Result<String, String> {
-  let mut cmd = CommandBuilder::new("telnet");
-  cmd.arg(&host);
-  cmd.arg(port.unwrap_or(23).to_string());
+async fn start_pty(app: tauri::AppHandle, state: State<'_, Arc<PtyRegistry>>, reactor: State<'_, Arc<Reactor>>, profiles: State<'_, Arc<ProfileStore>>, host: String, port: Option<u16>, cols: Option<u16>, rows: Option<u16>, mode: Option<String>, record_path: Option<String>, profile: Option<String>, label: Option<String>) -> Result<String, String> {
+  if mode.as_deref() == Some("native") {
+    return start_native_telnet(app, state.inner().clone(), reactor.inner().clone(), host, port, cols, rows, record_path).await;
+  }
+
+  // Resolve the launch template: a named profile, or the built-in telnet default.
+  let port = port.unwrap_or(23);
+  let label = label.unwrap_or_else(|| host.clone());
+  let template = match &profile {
+    Some(name) => profiles.get(name).ok_or_else(|| format!("unknown profile: {name}"))?,
+    None => ConnectionProfile::telnet(),
+  };
+  let cmd = template.build(&host, port, &label);
 
+  let cols = cols.unwrap_or(80);
+  let rows = rows.unwrap_or(24);
   let pty_system = native_pty_system();
   let pair = pty_system
-    .openpty(PtySize { cols: cols.unwrap_or(80), rows: rows.unwrap_or(24), pixel_width: 0, pixel_height: 0 })
+    .openpty(PtySize { cols, rows, pixel_width: 0, pixel_height: 0 })
     .map_err(|e| format!("openpty: {e}"))?;
-  let child = pair.slave.spawn_command(cmd).map_err(|e| format!("spawn telnet: {e}"))?;
+  let child = pair.slave.spawn_command(cmd).map_err(|e| format!("spawn {}: {e}", template.command))?;
 
   let mut reader = pair.master.try_clone_reader().map_err(|e| format!("reader: {e}"))?;
   let writer = pair.master.take_writer().map_err(|e| format!("writer: {e}"))?;
   let id = nanoid::nanoid!();
 
+  let start = Instant::now();
+  let recorder = new_recorder(record_path.as_deref(), cols, rows, start)?;
   {
     let mut guard = state.0.lock().map_err(|_| "lock ptys".to_string())?;
-    guard.insert(id.clone(), PtyEntry { child, pair, writer });
+    guard.insert(id.clone(), SessionEntry { kind: Session::Pty(PtyEntry { child, pair, writer }), start, cols, rows, recorder: recorder.clone() });
   }
 
+  // Scope note: the single reactor drives socket sessions (native telnet, raw
+  // TCP) only. PTY children keep a dedicated blocking reader thread. On Windows
+  // the ConPTY master exposes no pollable descriptor at all; on Unix the master
+  // fd is pollable, but putting it into non-blocking mode needs a libc/nix fcntl
+  // we don't depend on, and a blocking read behind a readiness hint would stall
+  // the reactor the way a spurious socket wakeup used to. One thread per PTY is
+  // the deliberate trade-off until that dependency is justified.
   let app_handle = app.clone();
   let id_clone = id.clone();
   std::thread::spawn(move || {
@@ -140,17 +669,114 @@ async fn start_pty(app: tauri::AppHandle, state: State<'_, Arc<PtyRegistry>>, ho
       match reader.read(&mut buf) {
         Ok(0) => break,
         Ok(n) => {
-          let _ = app_handle.emit("pty://data", &PtyData { id: id_clone.clone(), data: String::from_utf8_lossy(&buf[..n]).to_string() });
+          let data = String::from_utf8_lossy(&buf[..n]).to_string();
+          record_output(&recorder, &data);
+          let _ = app_handle.emit("pty://data", &PtyData { id: id_clone.clone(), data });
         }
         Err(_) => break,
       }
     }
     let _ = app_handle.emit("pty://exit", &PtyExit { id: id_clone.clone() });
   });
+  Ok(id)
+}
+
+/// Resolve `host:port` and connect to the first address that accepts, trying
+/// every resolved IPv4/IPv6 candidate in turn. `timeout` of `None` blocks with
+/// the OS default; `Some(t)` bounds each attempt. Returns the connected stream
+/// and the address that succeeded. Blocking — run it off the async runtime.
+fn connect_first(host: &str, port: u16, timeout: Option<Duration>) -> Result<(TcpStream, std::net::SocketAddr), String> {
+  let candidates = (host, port)
+    .to_socket_addrs()
+    .map_err(|e| format!("resolve {host}:{port}: {e}"))?;
+  let mut last_err = format!("no addresses resolved for {host}:{port}");
+  for addr in candidates {
+    let result = match timeout {
+      Some(t) => TcpStream::connect_timeout(&addr, t),
+      None => TcpStream::connect(addr),
+    };
+    match result {
+      Ok(stream) => return Ok((stream, addr)),
+      Err(e) => last_err = format!("connect {addr}: {e}"),
+    }
+  }
+  Err(last_err)
+}
+
+/// Open a native telnet session over a raw `TcpStream`, negotiating options
+/// inline instead of launching the external `telnet` binary.
+async fn start_native_telnet(app: tauri::AppHandle, registry: Arc<PtyRegistry>, reactor: Arc<Reactor>, host: String, port: Option<u16>, cols: Option<u16>, rows: Option<u16>, record_path: Option<String>) -> Result<String, String> {
+  let connect_port = port.unwrap_or(23);
+  let (stream, _addr) = tauri::async_runtime::spawn_blocking(move || connect_first(&host, connect_port, Some(Duration::from_millis(10_000))))
+    .await
+    .map_err(|e| format!("connect task: {e}"))??;
+  let reader = stream.try_clone().map_err(|e| format!("clone socket: {e}"))?;
+  let desc = socket_desc(&reader);
+  // One shared write half funnels keystrokes, resizes, and negotiation replies.
+  let writer = Arc::new(Mutex::new(stream));
+
+  let cols = cols.unwrap_or(80);
+  let rows = rows.unwrap_or(24);
+  // Offer to manage the window size up front; the actual NAWS subnegotiation is
+  // withheld until the peer agrees with `DO NAWS`, which the parser answers.
+  if let Ok(mut sock) = writer.lock() {
+    let _ = sock.write_all(&[IAC, WILL, OPT_NAWS]);
+  }
+
+  let id = nanoid::nanoid!();
+  let start = Instant::now();
+  let recorder = new_recorder(record_path.as_deref(), cols, rows, start)?;
+  {
+    let mut guard = registry.0.lock().map_err(|_| "lock ptys".to_string())?;
+    guard.insert(id.clone(), SessionEntry { kind: Session::Native(NativeSession { writer: writer.clone() }), start, cols, rows, recorder: recorder.clone() });
+  }
 
+  reactor.register(Source {
+    id: id.clone(),
+    desc,
+    socket: reader,
+    recorder,
+    parser: Some(TelnetParser::new(cols, rows)),
+    replies: Some(writer),
+    app,
+  })?;
   Ok(id)
 }
 
+/// Connect to a plain TCP service and stream bytes in both directions with no
+/// telnet or PTY layer, the way `nc host port` behaves. The resolved address we
+/// actually connected through is reported back so the frontend can show it.
+#[tauri::command]
+async fn start_tcp(app: tauri::AppHandle, state: State<'_, Arc<PtyRegistry>>, reactor: State<'_, Arc<Reactor>>, host: String, port: u16, timeout_ms: Option<u64>) -> Result<TcpConnection, String> {
+  // `0` or an absent timeout means "block with the OS default"; a positive value
+  // bounds each connection attempt.
+  let timeout = match timeout_ms {
+    None | Some(0) => None,
+    Some(ms) => Some(Duration::from_millis(ms)),
+  };
+  let (stream, addr) = tauri::async_runtime::spawn_blocking(move || connect_first(&host, port, timeout))
+    .await
+    .map_err(|e| format!("connect task: {e}"))??;
+
+  let reader = stream.try_clone().map_err(|e| format!("clone socket: {e}"))?;
+  let registry_writer = stream.try_clone().map_err(|e| format!("clone socket: {e}"))?;
+  let desc = socket_desc(&reader);
+
+  let id = nanoid::nanoid!();
+  let start = Instant::now();
+  let recorder: Arc<Mutex<Option<Recorder>>> = Arc::new(Mutex::new(None));
+  {
+    let mut guard = state.0.lock().map_err(|_| "lock ptys".to_string())?;
+    guard.insert(id.clone(), SessionEntry { kind: Session::Raw(RawSession { writer: registry_writer }), start, cols: 80, rows: 24, recorder: recorder.clone() });
+  }
+
+  reactor.register(Source { id: id.clone(), desc, socket: reader, recorder, parser: None, replies: None, app })?;
+  Ok(TcpConnection { id, addr: addr.to_string() })
+}
+
+#[derive(Serialize)]
+struct TcpConnection { id: String, addr: String }
+
 #[derive(Serialize)]
 struct PtyData { id: String, data: String }
 
@@ -159,26 +785,162 @@ struct PtyExit { id: String }
 
 #[tauri::command]
 async fn write_pty(state: State<'_, Arc<PtyRegistry>>, id: String, data: String) -> Result<(), String> {
-  let mut guard = state.0.lock().map_err(|_| "lock ptys".to_string())?;
-  let entry = guard.get_mut(&id).ok_or_else(|| "pty not found".to_string())?;
-  use std::io::Write;
-  entry.writer.write_all(data.as_bytes()).map_err(|e| format!("write: {e}"))?;
+  // A socket write can block on a back-pressuring peer, so resolve the target
+  // under the registry lock but perform the write after dropping it — otherwise a
+  // single slow session would freeze every command, `kill_pty` included.
+  enum Target { Done, Native(Arc<Mutex<TcpStream>>, Vec<u8>), Raw(TcpStream, Vec<u8>) }
+  let target = {
+    let mut guard = state.0.lock().map_err(|_| "lock ptys".to_string())?;
+    let entry = guard.get_mut(&id).ok_or_else(|| "pty not found".to_string())?;
+    match &mut entry.kind {
+      Session::Pty(p) => { p.writer.write_all(data.as_bytes()).map_err(|e| format!("write: {e}"))?; Target::Done }
+      // Escape any literal 0xFF so keystrokes can't be read as telnet commands.
+      Session::Native(n) => Target::Native(n.writer.clone(), escape_iac(data.as_bytes())),
+      Session::Raw(r) => Target::Raw(r.writer.try_clone().map_err(|e| format!("clone socket: {e}"))?, data.into_bytes()),
+    }
+  };
+  match target {
+    Target::Done => {}
+    Target::Native(writer, bytes) => {
+      let mut sock = writer.lock().map_err(|_| "lock socket".to_string())?;
+      write_all_nonblocking(&mut sock, &bytes).map_err(|e| format!("write: {e}"))?;
+    }
+    Target::Raw(mut sock, bytes) => write_all_nonblocking(&mut sock, &bytes).map_err(|e| format!("write: {e}"))?,
+  }
   Ok(())
 }
 
 #[tauri::command]
 async fn resize_pty(state: State<'_, Arc<PtyRegistry>>, id: String, cols: u16, rows: u16) -> Result<(), String> {
+  // Resolve under the lock, then write the NAWS update (which can block) after
+  // releasing the registry so other commands stay responsive.
+  let writer = {
+    let mut guard = state.0.lock().map_err(|_| "lock ptys".to_string())?;
+    let entry = guard.get_mut(&id).ok_or_else(|| "pty not found".to_string())?;
+    let writer = match &mut entry.kind {
+      Session::Pty(p) => { p.pair.master.resize(PtySize { cols, rows, pixel_width: 0, pixel_height: 0 }).map_err(|e| format!("resize: {e}"))?; None }
+      Session::Native(n) => Some(n.writer.clone()),
+      // A raw socket has no concept of a window size; ignore resizes.
+      Session::Raw(_) => None,
+    };
+    entry.cols = cols;
+    entry.rows = rows;
+    // Record the reflow so playback can track the window size.
+    if let Ok(mut recorder) = entry.recorder.lock() {
+      if let Some(rec) = recorder.as_mut() {
+        rec.event("r", &format!("{cols}x{rows}"));
+      }
+    }
+    writer
+  };
+  if let Some(writer) = writer {
+    let mut sock = writer.lock().map_err(|_| "lock socket".to_string())?;
+    write_all_nonblocking(&mut sock, &naws_subnegotiation(cols, rows)).map_err(|e| format!("resize: {e}"))?;
+  }
+  Ok(())
+}
+
+/// Begin recording an already-running session to a fresh `.cast` file, replacing
+/// any recorder currently attached. Event timestamps stay relative to the
+/// session's original spawn baseline.
+#[tauri::command]
+async fn start_recording(state: State<'_, Arc<PtyRegistry>>, id: String, path: String) -> Result<(), String> {
   let guard = state.0.lock().map_err(|_| "lock ptys".to_string())?;
   let entry = guard.get(&id).ok_or_else(|| "pty not found".to_string())?;
-  entry.pair.master.resize(PtySize { cols, rows, pixel_width: 0, pixel_height: 0 }).map_err(|e| format!("resize: {e}"))?;
+  let recorder = Recorder::create(&path, entry.cols, entry.rows, entry.start).map_err(|e| format!("record {path}: {e}"))?;
+  *entry.recorder.lock().map_err(|_| "lock recorder".to_string())? = Some(recorder);
   Ok(())
 }
 
+/// Stop recording a session, flushing and closing the current `.cast` file. A
+/// no-op if the session is not being recorded.
 #[tauri::command]
-async fn kill_pty(state: State<'_, Arc<PtyRegistry>>, id: String) -> Result<(), String> {
+async fn stop_recording(state: State<'_, Arc<PtyRegistry>>, id: String) -> Result<(), String> {
+  let guard = state.0.lock().map_err(|_| "lock ptys".to_string())?;
+  let entry = guard.get(&id).ok_or_else(|| "pty not found".to_string())?;
+  *entry.recorder.lock().map_err(|_| "lock recorder".to_string())? = None;
+  Ok(())
+}
+
+#[tauri::command]
+async fn kill_pty(state: State<'_, Arc<PtyRegistry>>, reactor: State<'_, Arc<Reactor>>, id: String) -> Result<(), String> {
+  // Pull the read half out of the poller first so no read races the close.
+  reactor.deregister(&id);
   let mut guard = state.0.lock().map_err(|_| "lock ptys".to_string())?;
-  if let Some(mut entry) = guard.remove(&id) {
-    let _ = entry.child.kill();
+  if let Some(entry) = guard.remove(&id) {
+    match entry.kind {
+      Session::Pty(mut p) => { let _ = p.child.kill(); }
+      Session::Native(n) => { if let Ok(sock) = n.writer.lock() { let _ = sock.shutdown(std::net::Shutdown::Both); } }
+      Session::Raw(r) => { let _ = r.writer.shutdown(std::net::Shutdown::Both); }
+    }
   }
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// `feed` the whole input and return the decoded data and negotiation replies.
+  fn decode(input: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let mut parser = TelnetParser::new(80, 24);
+    let (mut out, mut replies) = (Vec::new(), Vec::new());
+    parser.feed(input, &mut out, &mut replies);
+    (out, replies)
+  }
+
+  #[test]
+  fn escape_iac_doubles_only_ff() {
+    assert_eq!(escape_iac(&[0x01, IAC, 0x02, IAC]), vec![0x01, IAC, IAC, 0x02, IAC, IAC]);
+    assert_eq!(escape_iac(b"plain"), b"plain".to_vec());
+  }
+
+  #[test]
+  fn doubled_iac_unescapes_to_single_byte() {
+    let (out, replies) = decode(&[b'a', IAC, IAC, b'b']);
+    assert_eq!(out, vec![b'a', IAC, b'b']);
+    assert!(replies.is_empty());
+  }
+
+  #[test]
+  fn do_and_will_for_supported_option_are_accepted() {
+    // DO SGA -> WILL SGA, WILL SGA -> DO SGA.
+    assert_eq!(decode(&[IAC, DO, OPT_SGA]).1, vec![IAC, WILL, OPT_SGA]);
+    assert_eq!(decode(&[IAC, WILL, OPT_SGA]).1, vec![IAC, DO, OPT_SGA]);
+  }
+
+  #[test]
+  fn do_and_will_for_unsupported_option_are_refused() {
+    const OPT_ECHO: u8 = 1; // not in `we_support`
+    assert_eq!(decode(&[IAC, DO, OPT_ECHO]).1, vec![IAC, WONT, OPT_ECHO]);
+    assert_eq!(decode(&[IAC, WILL, OPT_ECHO]).1, vec![IAC, DONT, OPT_ECHO]);
+  }
+
+  #[test]
+  fn do_naws_replies_with_the_window_size_not_another_will() {
+    // cols = 255 (0x00FF), rows = 24. After we offer WILL NAWS, the peer's DO NAWS
+    // should draw out the sized subnegotiation rather than a second WILL.
+    let mut parser = TelnetParser::new(255, 24);
+    let (mut out, mut replies) = (Vec::new(), Vec::new());
+    parser.feed(&[IAC, DO, OPT_NAWS], &mut out, &mut replies);
+    assert!(out.is_empty());
+    assert_eq!(replies, naws_subnegotiation(255, 24));
+  }
+
+  #[test]
+  fn subnegotiation_is_stripped_from_the_data_stream() {
+    let input = [IAC, SB, OPT_NAWS, 0, 80, 0, 24, IAC, SE, b'x'];
+    let (out, replies) = decode(&input);
+    assert_eq!(out, vec![b'x']);
+    assert!(replies.is_empty());
+  }
+
+  #[test]
+  fn naws_encodes_big_endian_and_doubles_ff() {
+    // cols = 255 (0x00FF): the low byte 0xFF must be doubled; rows = 24.
+    assert_eq!(
+      naws_subnegotiation(255, 24),
+      vec![IAC, SB, OPT_NAWS, 0x00, 0xFF, 0xFF, 0x00, 0x18, IAC, SE],
+    );
+  }
+}